@@ -3,10 +3,12 @@ use node_template_runtime::{
 	SystemConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
+use serde::Deserialize;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{crypto::Ss58Codec, hashing::blake2_64, sr25519, Pair, Public};
 use sp_finality_grandpa::AuthorityId as GrandpaId;
 use sp_runtime::traits::{IdentifyAccount, Verify};
+use std::{collections::BTreeSet, path::Path};
 
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
@@ -16,9 +18,16 @@ pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
 
 /// Generate a crypto pair from seed.
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
+	try_get_from_seed::<TPublic>(seed).expect("static values are valid; qed")
+}
+
+/// Generate a crypto pair from a seed that may be invalid, e.g. one read from a file.
+pub fn try_get_from_seed<TPublic: Public>(
+	seed: &str,
+) -> Result<<TPublic::Pair as Pair>::Public, String> {
 	TPublic::Pair::from_string(&format!("//{}", seed), None)
-		.expect("static values are valid; qed")
-		.public()
+		.map(|pair| pair.public())
+		.map_err(|e| format!("invalid seed {:?}: {:?}", seed, e))
 }
 
 type AccountPublic = <Signature as Verify>::Signer;
@@ -36,6 +45,83 @@ pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
 	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
 }
 
+/// A single entry of an authorities file, see [`authorities_from_json`].
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AuthorityEntry {
+	/// A dev seed such as `"Alice"`, expanded like [`authority_keys_from_seed`].
+	Seed(String),
+	/// Explicit SS58 encoded public keys.
+	Keys { aura: String, grandpa: String },
+}
+
+/// Load the initial authorities from a JSON file.
+///
+/// The file holds an array whose entries are either a dev seed (`"Alice"`) or an object with
+/// SS58 encoded `aura` and `grandpa` public keys. Duplicate keys are rejected.
+pub fn authorities_from_json(path: &Path) -> Result<Vec<(AuraId, GrandpaId)>, String> {
+	parse_authorities_file(path, &read_authorities_file(path)?)
+}
+
+fn read_authorities_file(path: &Path) -> Result<Vec<u8>, String> {
+	std::fs::read(path)
+		.map_err(|e| format!("Error reading authorities file {}: {}", path.display(), e))
+}
+
+fn parse_authorities_file(path: &Path, json: &[u8]) -> Result<Vec<(AuraId, GrandpaId)>, String> {
+	parse_authorities(json)
+		.map_err(|e| format!("Error parsing authorities file {}: {}", path.display(), e))
+}
+
+fn parse_authorities(json: &[u8]) -> Result<Vec<(AuraId, GrandpaId)>, String> {
+	let entries: Vec<AuthorityEntry> = serde_json::from_slice(json).map_err(|e| e.to_string())?;
+	if entries.is_empty() {
+		return Err("no authorities given".into())
+	}
+
+	let mut authorities = Vec::with_capacity(entries.len());
+	for entry in entries {
+		authorities.push(match entry {
+			AuthorityEntry::Seed(seed) =>
+				(try_get_from_seed::<AuraId>(&seed)?, try_get_from_seed::<GrandpaId>(&seed)?),
+			AuthorityEntry::Keys { aura, grandpa } => (
+				AuraId::from_ss58check(&aura)
+					.map_err(|e| format!("invalid Aura key {}: {:?}", aura, e))?,
+				GrandpaId::from_ss58check(&grandpa)
+					.map_err(|e| format!("invalid GRANDPA key {}: {:?}", grandpa, e))?,
+			),
+		});
+	}
+
+	ensure_unique_authorities(&authorities)?;
+	Ok(authorities)
+}
+
+/// Check that no Aura or GRANDPA key appears more than once in `authorities`.
+fn ensure_unique_authorities(authorities: &[(AuraId, GrandpaId)]) -> Result<(), String> {
+	let mut aura_keys = BTreeSet::new();
+	let mut grandpa_keys = BTreeSet::new();
+	for (aura, grandpa) in authorities {
+		if !aura_keys.insert(aura) {
+			return Err(format!("duplicate Aura key {}", aura.to_ss58check()))
+		}
+		if !grandpa_keys.insert(grandpa) {
+			return Err(format!("duplicate GRANDPA key {}", grandpa.to_ss58check()))
+		}
+	}
+	Ok(())
+}
+
+/// Initial authorities of the development chain.
+fn development_authorities() -> Vec<(AuraId, GrandpaId)> {
+	vec![authority_keys_from_seed("Alice")]
+}
+
+/// Initial authorities of the built-in local testnet.
+fn local_testnet_authorities() -> Vec<(AuraId, GrandpaId)> {
+	vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")]
+}
+
 pub fn development_config() -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
@@ -49,7 +135,7 @@ pub fn development_config() -> Result<ChainSpec, String> {
 			testnet_genesis(
 				wasm_binary,
 				// Initial PoA authorities
-				vec![authority_keys_from_seed("Alice")],
+				development_authorities(),
 				// Sudo account
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
 				// Pre-funded accounts
@@ -76,19 +162,50 @@ pub fn development_config() -> Result<ChainSpec, String> {
 }
 
 pub fn local_testnet_config() -> Result<ChainSpec, String> {
+	local_testnet_config_with("Local Testnet", "local_testnet", local_testnet_authorities())
+}
+
+/// Local testnet whose initial authorities are read from `path`, see [`authorities_from_json`].
+///
+/// The chain ID is derived from the file name and a hash of the file contents, so files with
+/// different authorities never share a database directory with each other or with the built-in
+/// local testnet. Identical files share an ID, as they describe the same genesis.
+pub fn local_testnet_config_from_file(path: &Path) -> Result<ChainSpec, String> {
+	let json = read_authorities_file(path)?;
+	let authorities = parse_authorities_file(path, &json)?;
+	let stem = path.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+	let name = format!("Local Testnet ({})", stem);
+	local_testnet_config_with(&name, &custom_testnet_id(&stem, &json), authorities)
+}
+
+/// Chain ID of a local testnet built from an authorities file named `stem` holding `json`.
+fn custom_testnet_id(stem: &str, json: &[u8]) -> String {
+	let stem: String = stem
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+		.collect();
+	let hash: String = blake2_64(json)[..4].iter().map(|b| format!("{:02x}", b)).collect();
+	format!("local_testnet_{}_{}", stem, hash)
+}
+
+fn local_testnet_config_with(
+	name: &str,
+	id: &str,
+	initial_authorities: Vec<(AuraId, GrandpaId)>,
+) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
 	Ok(ChainSpec::from_genesis(
 		// Name
-		"Local Testnet",
+		name,
 		// ID
-		"local_testnet",
+		id,
 		ChainType::Local,
 		move || {
 			testnet_genesis(
 				wasm_binary,
 				// Initial PoA authorities
-				vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")],
+				initial_authorities.clone(),
 				// Sudo account
 				get_account_id_from_seed::<sr25519::Public>("Alice"),
 				// Pre-funded accounts
@@ -130,6 +247,12 @@ fn testnet_genesis(
 	endowed_accounts: Vec<AccountId>,
 	_enable_println: bool,
 ) -> GenesisConfig {
+	if cfg!(debug_assertions) {
+		if let Err(e) = ensure_unique_authorities(&initial_authorities) {
+			panic!("invalid genesis authorities: {}", e)
+		}
+	}
+
 	GenesisConfig {
 		system: SystemConfig {
 			// Add Wasm runtime to storage.
@@ -152,3 +275,115 @@ fn testnet_genesis(
 		transaction_payment: Default::default(),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn genesis_with(authorities: Vec<(AuraId, GrandpaId)>) -> GenesisConfig {
+		let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+		testnet_genesis(&[], authorities, alice.clone(), vec![alice], true)
+	}
+
+	#[test]
+	fn distinct_authorities_are_accepted() {
+		let genesis =
+			genesis_with(vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Bob")]);
+		assert_eq!(genesis.aura.authorities.len(), 2);
+		assert_eq!(genesis.grandpa.authorities.len(), 2);
+	}
+
+	#[test]
+	fn built_in_specs_have_unique_authorities() {
+		assert_eq!(ensure_unique_authorities(&development_authorities()), Ok(()));
+		assert_eq!(ensure_unique_authorities(&local_testnet_authorities()), Ok(()));
+	}
+
+	#[test]
+	fn duplicate_grandpa_keys_are_detected() {
+		let alice = authority_keys_from_seed("Alice");
+		let bob = authority_keys_from_seed("Bob");
+		let result = ensure_unique_authorities(&[alice.clone(), (bob.0, alice.1)]);
+		assert!(result.unwrap_err().starts_with("duplicate GRANDPA key"));
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "duplicate Aura key")]
+	fn duplicate_authorities_are_rejected() {
+		genesis_with(vec![authority_keys_from_seed("Alice"), authority_keys_from_seed("Alice")]);
+	}
+
+	#[test]
+	fn authorities_are_parsed_from_seeds_and_keys() {
+		let (aura, grandpa) = authority_keys_from_seed("Charlie");
+		let json = format!(
+			r#"["Alice", "Bob", {{ "aura": "{}", "grandpa": "{}" }}]"#,
+			aura.to_ss58check(),
+			grandpa.to_ss58check(),
+		);
+
+		assert_eq!(
+			parse_authorities(json.as_bytes()).unwrap(),
+			vec![
+				authority_keys_from_seed("Alice"),
+				authority_keys_from_seed("Bob"),
+				authority_keys_from_seed("Charlie"),
+			],
+		);
+	}
+
+	#[test]
+	fn invalid_authorities_files_are_rejected() {
+		assert!(parse_authorities(br#"[]"#).is_err());
+		assert!(parse_authorities(br#"["Alice", "Alice"]"#).is_err());
+		assert!(parse_authorities(br#"[{ "aura": "nope", "grandpa": "nope" }]"#).is_err());
+		assert!(parse_authorities(br#"[""]"#).is_err());
+		assert!(parse_authorities(br#"["Alice//"]"#).is_err());
+	}
+
+	#[test]
+	fn authorities_are_loaded_from_a_file() {
+		let dir = std::env::temp_dir();
+		let valid = dir.join(format!("node-template-authorities-{}.json", std::process::id()));
+		let invalid = dir.join(format!("node-template-invalid-{}.json", std::process::id()));
+		std::fs::write(&valid, br#"["Alice", "Bob"]"#).unwrap();
+		std::fs::write(&invalid, br#"{ "Alice": 1 }"#).unwrap();
+
+		let loaded = authorities_from_json(&valid);
+		let malformed = authorities_from_json(&invalid);
+		std::fs::remove_file(&valid).unwrap();
+		std::fs::remove_file(&invalid).unwrap();
+
+		assert_eq!(loaded, Ok(local_testnet_authorities()));
+		assert!(malformed.unwrap_err().starts_with("Error parsing authorities file"));
+		assert!(authorities_from_json(&valid)
+			.unwrap_err()
+			.starts_with("Error reading authorities file"));
+	}
+
+	#[test]
+	fn custom_testnets_get_their_own_chain_id() {
+		let dir = std::env::temp_dir().join(format!("node-template-ids-{}", std::process::id()));
+		let (a, b) = (dir.join("a").join("validators.json"), dir.join("b").join("validators.json"));
+		for (path, json) in [(&a, &br#"["Alice"]"#[..]), (&b, &br#"["Bob"]"#[..])] {
+			std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+			std::fs::write(path, json).unwrap();
+		}
+
+		let id_of = |path: &Path| {
+			let stem = path.file_stem().unwrap().to_string_lossy();
+			custom_testnet_id(&stem, &read_authorities_file(path).unwrap())
+		};
+		let (id_a, id_b) = (id_of(&a), id_of(&b));
+		std::fs::remove_dir_all(&dir).unwrap();
+
+		assert!(id_a.starts_with("local_testnet_validators_"));
+		assert_eq!(id_a.len(), "local_testnet_validators_".len() + 8);
+		assert_ne!(id_a, id_b);
+		assert_eq!(id_a, custom_testnet_id("validators", br#"["Alice"]"#));
+		assert!(
+			custom_testnet_id("My Validators", b"[]").starts_with("local_testnet_my_validators_")
+		);
+	}
+}
@@ -1,4 +1,5 @@
-use sc_cli::RunCmd;
+use node_template_runtime::BlockNumber;
+use sc_consensus_slots::{BackoffAuthoringOnFinalizedHeadLagging, SlotProportion};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -10,6 +11,88 @@ pub struct Cli {
 	pub run: RunCmd,
 }
 
+#[derive(Debug, StructOpt)]
+pub struct RunCmd {
+	#[structopt(flatten)]
+	pub base: sc_cli::RunCmd,
+
+	/// Back off block authoring once more than this many blocks are left unfinalized.
+	///
+	/// Authoring never backs off when this is not set.
+	#[structopt(long, value_name = "BLOCKS")]
+	pub backoff_authoring_slack: Option<BlockNumber>,
+
+	/// Maximum number of slots to skip between authored blocks while backing off.
+	///
+	/// Defaults to 100. Only valid together with `--backoff-authoring-slack`.
+	#[structopt(long, value_name = "SLOTS", requires = "backoff-authoring-slack")]
+	pub backoff_authoring_max_interval: Option<BlockNumber>,
+
+	/// Portion of the slot, between 0 and 1, the proposer may spend building a block.
+	///
+	/// Defaults to 2/3.
+	#[structopt(long, value_name = "PORTION", parse(try_from_str = parse_slot_portion))]
+	pub block_proposal_slot_portion: Option<f32>,
+
+	/// Upper bound, between 0 and 1, on the slot portion used when the proposal time is extended
+	/// after missed slots.
+	///
+	/// Must be at least `--block-proposal-slot-portion`, which defaults to 2/3.
+	#[structopt(long, value_name = "PORTION", parse(try_from_str = parse_slot_portion))]
+	pub max_block_proposal_slot_portion: Option<f32>,
+}
+
+fn parse_slot_portion(portion: &str) -> Result<f32, String> {
+	let portion = portion.parse::<f32>().map_err(|e| e.to_string())?;
+	if (0.0..=1.0).contains(&portion) {
+		Ok(portion)
+	} else {
+		Err(format!("slot portion must be between 0 and 1, got {}", portion))
+	}
+}
+
+impl RunCmd {
+	/// The block authoring backoff strategy selected on the command line, if any.
+	pub fn backoff_authoring_blocks(
+		&self,
+	) -> Option<BackoffAuthoringOnFinalizedHeadLagging<BlockNumber>> {
+		self.backoff_authoring_slack.map(|unfinalized_slack| {
+			let mut backoff =
+				BackoffAuthoringOnFinalizedHeadLagging { unfinalized_slack, ..Default::default() };
+			if let Some(max_interval) = self.backoff_authoring_max_interval {
+				backoff.max_interval = max_interval;
+			}
+			backoff
+		})
+	}
+
+	/// The portion of each slot given to the block proposer.
+	pub fn block_proposal_slot_portion(&self) -> SlotProportion {
+		SlotProportion::new(self.block_proposal_slot_portion.unwrap_or(2f32 / 3f32))
+	}
+
+	/// The upper bound on the proposer's slot portion, if any.
+	pub fn max_block_proposal_slot_portion(&self) -> Option<SlotProportion> {
+		self.max_block_proposal_slot_portion.map(SlotProportion::new)
+	}
+
+	/// Check the options that cannot be validated one argument at a time.
+	pub fn validate(&self) -> Result<(), String> {
+		if let Some(max) = self.max_block_proposal_slot_portion() {
+			let base = self.block_proposal_slot_portion();
+			if max.get() < base.get() {
+				return Err(format!(
+					"--max-block-proposal-slot-portion ({}) must not be below the block proposal \
+					 slot portion ({})",
+					max.get(),
+					base.get(),
+				))
+			}
+		}
+		Ok(())
+	}
+}
+
 #[derive(Debug, StructOpt)]
 pub enum Subcommand {
 	/// Key management cli utilities
@@ -39,3 +122,86 @@ pub enum Subcommand {
 	#[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
 	Benchmark(frame_benchmarking_cli::BenchmarkCmd),
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sc_consensus_slots::BackoffAuthoringBlocksStrategy;
+
+	fn run_cmd(args: &[&str]) -> RunCmd {
+		Cli::from_iter(std::iter::once("node-template").chain(args.iter().copied())).run
+	}
+
+	#[test]
+	fn backoff_is_disabled_by_default() {
+		assert!(run_cmd(&[]).backoff_authoring_blocks().is_none());
+	}
+
+	#[test]
+	fn backoff_suppresses_authoring_when_finality_lags() {
+		let backoff = run_cmd(&["--backoff-authoring-slack", "10"])
+			.backoff_authoring_blocks()
+			.expect("backoff was enabled on the command line");
+		assert_eq!(backoff.unfinalized_slack, 10);
+		assert_eq!(backoff.max_interval, 100);
+
+		// 100 unfinalized blocks is well past the slack of 10.
+		assert!(backoff.should_backoff(100, 1_000.into(), 0, 1_001.into(), "test"));
+		// Within the slack authoring carries on as usual.
+		assert!(!backoff.should_backoff(100, 1_000.into(), 95, 1_001.into(), "test"));
+	}
+
+	#[test]
+	fn backoff_max_interval_requires_the_slack() {
+		let args = ["node-template", "--backoff-authoring-max-interval", "20"];
+		assert!(Cli::from_iter_safe(&args).is_err());
+
+		let backoff =
+			run_cmd(&["--backoff-authoring-slack", "10", "--backoff-authoring-max-interval", "20"])
+				.backoff_authoring_blocks()
+				.expect("backoff was enabled on the command line");
+		assert_eq!(backoff.max_interval, 20);
+	}
+
+	#[test]
+	fn slot_portions_default_to_two_thirds() {
+		let run = run_cmd(&[]);
+		assert_eq!(run.block_proposal_slot_portion().get(), 2f32 / 3f32);
+		assert!(run.max_block_proposal_slot_portion().is_none());
+	}
+
+	#[test]
+	fn slot_portions_are_taken_from_the_command_line() {
+		let run = run_cmd(&[
+			"--block-proposal-slot-portion",
+			"0.5",
+			"--max-block-proposal-slot-portion",
+			"0.8",
+		]);
+		assert_eq!(run.block_proposal_slot_portion().get(), 0.5);
+		assert_eq!(run.max_block_proposal_slot_portion().map(|p| p.get()), Some(0.8));
+	}
+
+	#[test]
+	fn slot_portions_outside_the_unit_range_are_rejected() {
+		let args = ["node-template", "--block-proposal-slot-portion", "1.5"];
+		assert!(Cli::from_iter_safe(&args).is_err());
+		let args = ["node-template", "--max-block-proposal-slot-portion", "2"];
+		assert!(Cli::from_iter_safe(&args).is_err());
+	}
+
+	#[test]
+	fn max_slot_portion_below_the_base_portion_is_rejected() {
+		let run = run_cmd(&[
+			"--block-proposal-slot-portion",
+			"0.8",
+			"--max-block-proposal-slot-portion",
+			"0.3",
+		]);
+		assert!(run.validate().is_err());
+		// The default base portion of 2/3 applies when only the max is given.
+		assert!(run_cmd(&["--max-block-proposal-slot-portion", "0.5"]).validate().is_err());
+		assert!(run_cmd(&["--max-block-proposal-slot-portion", "0.9"]).validate().is_ok());
+		assert!(run_cmd(&[]).validate().is_ok());
+	}
+}
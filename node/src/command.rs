@@ -36,8 +36,13 @@ impl SubstrateCli for Cli {
 		Ok(match id {
 			"dev" => Box::new(chain_spec::development_config()?),
 			"" | "local" => Box::new(chain_spec::local_testnet_config()?),
-			path =>
-				Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(path))?),
+			id => match id.strip_prefix("local:") {
+				Some(authorities) => Box::new(chain_spec::local_testnet_config_from_file(
+					std::path::Path::new(authorities),
+				)?),
+				None =>
+					Box::new(chain_spec::ChainSpec::from_json_file(std::path::PathBuf::from(id))?),
+			},
 		})
 	}
 
@@ -109,9 +114,19 @@ pub fn run() -> sc_cli::Result<()> {
 					.into())
 			},
 		None => {
-			let runner = cli.create_runner(&cli.run)?;
+			cli.run.validate().map_err(sc_cli::Error::Input)?;
+			let runner = cli.create_runner(&cli.run.base)?;
+			let backoff_authoring_blocks = cli.run.backoff_authoring_blocks();
+			let block_proposal_slot_portion = cli.run.block_proposal_slot_portion();
+			let max_block_proposal_slot_portion = cli.run.max_block_proposal_slot_portion();
 			runner.run_node_until_exit(|config| async move {
-				service::new_full(config).map_err(sc_cli::Error::Service)
+				service::new_full(
+					config,
+					backoff_authoring_blocks,
+					block_proposal_slot_portion,
+					max_block_proposal_slot_portion,
+				)
+				.map_err(sc_cli::Error::Service)
 			})
 		},
 	}
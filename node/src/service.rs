@@ -1,8 +1,9 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
-use node_template_runtime::{self, opaque::Block, RuntimeApi};
+use node_template_runtime::{self, opaque::Block, BlockNumber, RuntimeApi};
 use sc_client_api::ExecutorProvider;
-use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
+use sc_consensus_aura::{ImportQueueParams, StartAuraParams};
+use sc_consensus_slots::{BackoffAuthoringOnFinalizedHeadLagging, SlotProportion};
 pub use sc_executor::NativeElseWasmExecutor;
 use sc_finality_grandpa::SharedVoterState;
 use sc_keystore::LocalKeystore;
@@ -157,7 +158,16 @@ fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+///
+/// When `backoff_authoring_blocks` is set, the node slows down block authoring while finality
+/// lags behind the best block. The slot portions bound how long the proposer may spend building
+/// each block.
+pub fn new_full(
+	mut config: Configuration,
+	backoff_authoring_blocks: Option<BackoffAuthoringOnFinalizedHeadLagging<BlockNumber>>,
+	block_proposal_slot_portion: SlotProportion,
+	max_block_proposal_slot_portion: Option<SlotProportion>,
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -209,7 +219,6 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 
 	let role = config.role.clone();
 	let force_authoring = config.force_authoring;
-	let backoff_authoring_blocks: Option<()> = None;
 	let name = config.network.node_name.clone();
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
@@ -278,8 +287,8 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 				can_author_with,
 				sync_oracle: network.clone(),
 				justification_sync_link: network.clone(),
-				block_proposal_slot_portion: SlotProportion::new(2f32 / 3f32),
-				max_block_proposal_slot_portion: None,
+				block_proposal_slot_portion,
+				max_block_proposal_slot_portion,
 				telemetry: telemetry.as_ref().map(|x| x.handle()),
 			},
 		)?;